@@ -1,15 +1,61 @@
 extern crate tantivy;
+#[macro_use]
+extern crate serde;
+
 use tantivy::directory::MmapDirectory;
 use tantivy::schema::*;
+use tantivy::tokenizer::{Language, LowerCaser, NgramTokenizer, SimpleTokenizer, Stemmer, TextAnalyzer};
 use tantivy::Index;
 use tantivy::IndexWriter;
 use tantivy::TantivyError;
 
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::Path;
 use std::time::Instant;
 
+/// Where a field's value is read from in each CSV record: either a fixed column
+/// index, or a column located by its header name. The two shapes are told apart
+/// by which key is present, so the config stays terse.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum ColumnSource {
+    Index { index: usize },
+    Header { header: String },
+}
+
+/// Declaration of a single field: its name, its Tantivy type, the index flags to
+/// apply (`TEXT`/`STRING`/`STORED`/`fast`) and the CSV column it is fed from.
+#[derive(Debug, Clone, Deserialize)]
+struct FieldConfig {
+    name: String,
+    #[serde(rename = "type")]
+    field_type: String,
+    #[serde(default)]
+    flags: Vec<String>,
+    /// Optional named tokenizer to analyse this field with (e.g. `stem_en` or
+    /// `autocomplete`). Defaults to the built-in `default` tokenizer.
+    #[serde(default)]
+    tokenizer: Option<String>,
+    column: ColumnSource,
+}
+
+/// The whole schema configuration: an ordered list of field declarations loaded
+/// from a JSON file at startup.
+#[derive(Debug, Clone, Deserialize)]
+struct SchemaConfig {
+    fields: Vec<FieldConfig>,
+}
+
+/// A resolved field: the `Field` handle created in the schema together with the
+/// original declaration, so the indexing loop knows how to read and convert the
+/// CSV value for it.
+struct FieldMapping {
+    field: Field,
+    config: FieldConfig,
+}
+
 /// Get the current directory.
 ///
 /// This function returns a `String` representing the current directory.
@@ -19,41 +65,102 @@ fn get_current_dir() -> String {
     current_dir_str
 }
 
-/// Creates the schema for the Tantivy index.
-/// Returns the created schema.
-fn create_schema() -> Schema {
-    // DEFINING THE SCHEMA:
-    // The Tantivy index requires a very strict schema. The schema declares which fields are in the index,
-    // and for each field, its type and "the way it should be indexed".
-    // First, we need to define a schema...
-    let mut schema_builder = Schema::builder();
+/// Load the schema configuration from the JSON file at `config_path`.
+fn load_config(config_path: &str) -> SchemaConfig {
+    let contents = fs::read_to_string(config_path)
+        .unwrap_or_else(|err| panic!("Failed to read schema config {}: {:?}", config_path, err));
+    serde_json::from_str(&contents)
+        .unwrap_or_else(|err| panic!("Failed to parse schema config {}: {:?}", config_path, err))
+}
 
-    // title;url;body;state
-    // Our first field is the title of the web page.
-    // We want full-text search for it, and we also want to be able to retrieve the document after the search.
-    // TEXT | STORED is some syntactic sugar to describe that.
-    // TEXT means the field should be tokenized and indexed, along with its term frequency and term positions.
-    // STORED means that the field will also be saved in a compressed, row-oriented key-value store.
-    // This store is useful to reconstruct the documents that were selected during the search phase.
-    schema_builder.add_text_field("title", TEXT | STORED);
+/// Build `TextOptions` for a text/string field from its declared flags. `STRING`
+/// indexes the value verbatim, `TEXT` tokenizes it (with the field's named
+/// tokenizer, falling back to `default`), and `STORED` keeps it in the doc store
+/// so it can be returned after a search.
+fn build_text_options(config: &FieldConfig) -> TextOptions {
+    let mut options = TextOptions::default();
+    for flag in &config.flags {
+        match flag.as_str() {
+            "TEXT" => {
+                let tokenizer = config.tokenizer.as_deref().unwrap_or("default");
+                options = options.set_indexing_options(
+                    TextFieldIndexing::default()
+                        .set_tokenizer(tokenizer)
+                        .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+                );
+            }
+            "STRING" => {
+                options = options.set_indexing_options(
+                    TextFieldIndexing::default()
+                        .set_tokenizer("raw")
+                        .set_index_option(IndexRecordOption::Basic),
+                );
+            }
+            "STORED" => options = options.set_stored(),
+            other => println!("Ignoring unsupported text flag: {}", other),
+        }
+    }
+    options
+}
 
-    // The second field is the URL of the web page.
-    // This field is non-searchable but used as metadata.
-    schema_builder.add_text_field("url", STORED);
+/// Register the custom tokenizer pipelines referenced by the schema config on
+/// the index. `stem_en` lower-cases and stems English tokens for morphological
+/// matching on the body, and `autocomplete` emits prefix edge-ngrams (2..=10
+/// chars) for as-you-type prefix queries. This must run before indexing and be
+/// mirrored on the query side so analysis stays consistent.
+fn register_tokenizers(index: &Index) {
+    let stem_en = TextAnalyzer::from(SimpleTokenizer)
+        .filter(LowerCaser)
+        .filter(Stemmer::new(Language::English));
+    index.tokenizers().register("stem_en", stem_en);
+
+    let autocomplete = TextAnalyzer::from(NgramTokenizer::new(2, 10, true)).filter(LowerCaser);
+    index.tokenizers().register("autocomplete", autocomplete);
+}
 
-    // Our third field is the body of the web page.
-    // We want full-text search for it, but we do not need to be able to retrieve it for our application.
-    // We can make our index lighter by omitting the STORED flag.
-    schema_builder.add_text_field("body", TEXT | STORED);
+/// Build `NumericOptions` for a numeric field from its declared flags. `INDEXED`
+/// makes it searchable, `fast` stores it as a fast field for sorting, and
+/// `STORED` keeps it in the doc store.
+fn build_numeric_options(flags: &[String]) -> NumericOptions {
+    let mut options = NumericOptions::default();
+    for flag in flags {
+        match flag.as_str() {
+            "INDEXED" => options = options | INDEXED,
+            "STORED" => options = options | STORED,
+            "fast" | "FAST" => options = options | FAST,
+            other => println!("Ignoring unsupported numeric flag: {}", other),
+        }
+    }
+    options
+}
 
-    // The fourth field is the state (if it exists) where the company that owns the URL is located.
-    // This field is searchable.
-    schema_builder.add_text_field("state", TEXT | STORED);
+/// Build the schema dynamically from the configuration and return it together
+/// with the per-field mapping that drives the indexing loop.
+///
+/// This replaces the previous hardcoded `title/url/body/state` schema: the field
+/// set, their types, their flags and the CSV columns they come from are now all
+/// data, so the crate can index arbitrary CSVs without code edits.
+fn build_schema(config: &SchemaConfig) -> (Schema, Vec<FieldMapping>) {
+    let mut schema_builder = Schema::builder();
+    let mut mappings: Vec<FieldMapping> = Vec::new();
 
-    // Create the schema
-    let schema = schema_builder.build();
+    for field_config in &config.fields {
+        let field = match field_config.field_type.as_str() {
+            "text" | "string" => {
+                schema_builder.add_text_field(&field_config.name, build_text_options(field_config))
+            }
+            "u64" => schema_builder.add_u64_field(&field_config.name, build_numeric_options(&field_config.flags)),
+            "i64" => schema_builder.add_i64_field(&field_config.name, build_numeric_options(&field_config.flags)),
+            "f64" => schema_builder.add_f64_field(&field_config.name, build_numeric_options(&field_config.flags)),
+            other => panic!("Unsupported field type in schema config: {}", other),
+        };
+        mappings.push(FieldMapping {
+            field,
+            config: field_config.clone(),
+        });
+    }
 
-    schema
+    (schema_builder.build(), mappings)
 }
 
 /// Creates a new index with the provided schema.
@@ -85,12 +192,23 @@ fn find_files(location: &str, pattern: &str) -> Result<Vec<std::path::PathBuf>,
     Ok(files)
 }
 
+/// Resolve the CSV column index for a field mapping. Fixed indices are used as
+/// is; header names are looked up in the per-file header map built from the CSV
+/// header row.
+fn resolve_column(source: &ColumnSource, headers: &HashMap<String, usize>) -> Option<usize> {
+    match source {
+        ColumnSource::Index { index } => Some(*index),
+        ColumnSource::Header { header } => headers.get(header).copied(),
+    }
+}
+
 /// Indexes the contents of a CSV file into a Tantivy index.
 ///
 /// # Arguments
 ///
-/// * `file_path` - The path to the CSV file.
-/// * `schema` - The Tantivy schema.
+/// * `files_path` - The location to scan for CSV files.
+/// * `mappings` - The field mapping describing how each schema field is read
+///   from a CSV record.
 /// * `index_writer` - The Tantivy index writer.
 ///
 /// # Errors
@@ -98,7 +216,7 @@ fn find_files(location: &str, pattern: &str) -> Result<Vec<std::path::PathBuf>,
 /// Returns an error if there is any issue reading the CSV file or indexing the documents.
 fn index_data(
     files_path: &str,
-    schema: &Schema,
+    mappings: &[FieldMapping],
     index_writer: &mut Result<IndexWriter, TantivyError>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Patron de archivos que se indexarán
@@ -123,6 +241,15 @@ fn index_data(
         };
         let mut reader = csv::Reader::from_reader(file);
 
+        // Build a header -> column index map so fields mapped by header name can
+        // be resolved for this file.
+        let mut headers: HashMap<String, usize> = HashMap::new();
+        if let Ok(header_record) = reader.headers() {
+            for (idx, header) in header_record.iter().enumerate() {
+                headers.insert(header.to_string(), idx);
+            }
+        }
+
         let mut counter: i32 = 0;
         let mut exception_counter: i32 = 0;
 
@@ -142,45 +269,35 @@ fn index_data(
             // Create a new document
             let mut doc = Document::default();
 
-            // Add fields to the document
-            // Check for title
-            // Posicion de las columnas en los datos: 
-            // 0,  1  , 2 , 3  , 4,  5
-            //  ,title,URL,Body,id,states
-            let mut _title: String = String::new();
-            if schema.get_field("title").is_ok() {
-                _title = record.get(1).unwrap_or("NA").to_string();
-            } else {
-                _title = "NA".to_string();
-            }
-            doc.add_text(schema.get_field("title").unwrap(), _title);
-
-            // Check for url
-            let mut _url: String = String::new();
-            if schema.get_field("url").is_ok() {
-                _url = record.get(2).unwrap_or("NA").to_string();
-            } else {
-                _url = "NA".to_string();
-            }
-            doc.add_text(schema.get_field("url").unwrap(), _url);
-
-            // Check for body
-            let mut _body: String = String::new();
-            if schema.get_field("body").is_ok() {
-                _body = record.get(3).unwrap_or("NA").to_string();
-            } else {
-                _body = "NA".to_string();
-            }
-            doc.add_text(schema.get_field("body").unwrap(), _body);
-
-            // Check for states
-            let mut _state: String = String::new();
-            if schema.get_field("state").is_ok() {
-                _state = record.get(5).unwrap_or("NA").to_string();
-            } else {
-                _state = "NA".to_string();
+            // Add each configured field to the document, reading the value from
+            // the CSV column declared for it and converting it to the field's
+            // type.
+            for mapping in mappings {
+                let column = resolve_column(&mapping.config.column, &headers);
+                let raw_value = column
+                    .and_then(|idx| record.get(idx))
+                    .unwrap_or("NA");
+
+                match mapping.config.field_type.as_str() {
+                    "text" | "string" => doc.add_text(mapping.field, raw_value),
+                    "u64" => {
+                        if let Ok(value) = raw_value.parse::<u64>() {
+                            doc.add_u64(mapping.field, value);
+                        }
+                    }
+                    "i64" => {
+                        if let Ok(value) = raw_value.parse::<i64>() {
+                            doc.add_i64(mapping.field, value);
+                        }
+                    }
+                    "f64" => {
+                        if let Ok(value) = raw_value.parse::<f64>() {
+                            doc.add_f64(mapping.field, value);
+                        }
+                    }
+                    _ => {}
+                }
             }
-            doc.add_text(schema.get_field("state").unwrap(), _state);
 
             // Add the document to the index writer
             if let Ok(ref mut writer) = *index_writer {
@@ -284,19 +401,28 @@ fn main() {
         let index_size = get_index_size(&index_path);
         println!("Tamaño del índice: {} megabytes", index_size);
     } else {
-        println!("Creating the schema for the index...");
-        let schema = create_schema();
+        // Load the field/column mapping from the schema config file.
+        let config_path = format!("{}/schema_config.json", current_path);
+        println!("Loading schema config from {}", config_path);
+        let config = load_config(&config_path);
+
+        println!("Building the schema for the index...");
+        let (schema, mappings) = build_schema(&config);
 
         println!("Creating the index...");
         let index = create_index(&index_path, schema.clone());
         println!("Index created successfully!");
 
+        // Register the custom tokenizer pipelines before indexing so the
+        // configured fields are analysed with them.
+        register_tokenizers(&index);
+
         let mut index_writer = index.writer(50_000_000);
         let data_path = "/home/ubuntu/work/lucene_tantivy_data";
-        
+
         println!("Start indexing files in {}", data_path);
         let start_index_time = Instant::now();
-        match index_data(data_path, &schema, &mut index_writer) {
+        match index_data(data_path, &mappings, &mut index_writer) {
             Ok(()) => println!("CSV file indexed successfully!"),
             Err(err) => eprintln!("Error indexing CSV file: {:?}", err),
         }
@@ -0,0 +1,194 @@
+extern crate tantivy;
+use tantivy::Index;
+use tantivy::collector::TopDocs;
+use tantivy::ReloadPolicy;
+use tantivy::query::QueryParser;
+use tantivy::schema::Field;
+use tantivy::schema::Schema;
+use tantivy::tokenizer::{Language, LowerCaser, NgramTokenizer, SimpleTokenizer, Stemmer, TextAnalyzer};
+
+use std::env;
+use std::fs;
+use std::time::{Duration, Instant};
+
+/// Get the current directory.
+///
+/// This function returns a `String` representing the current directory.
+fn get_current_dir() -> String {
+    let current_dir = env::current_dir().expect("Failed to get current directory");
+    let current_dir_str = current_dir.to_string_lossy().into_owned();
+    current_dir_str
+}
+
+/// Check if an index exists at the given path.
+fn index_exists(index_path: &str) -> bool {
+    Index::open_in_dir(index_path).is_ok()
+}
+
+/// Register the custom tokenizer pipelines on the index so query analysis uses
+/// the very same `stem_en` and `autocomplete` analysers that were applied at
+/// indexing time. Tokenizer registrations live in-process, so they have to be
+/// re-declared every time the index is opened.
+fn register_tokenizers(index: &Index) {
+    let stem_en = TextAnalyzer::from(SimpleTokenizer)
+        .filter(LowerCaser)
+        .filter(Stemmer::new(Language::English));
+    index.tokenizers().register("stem_en", stem_en);
+
+    let autocomplete = TextAnalyzer::from(NgramTokenizer::new(2, 10, true)).filter(LowerCaser);
+    index.tokenizers().register("autocomplete", autocomplete);
+}
+
+/// Read a newline-delimited query file and return the non-empty queries.
+fn read_queries(queries_path: &str) -> Vec<String> {
+    let contents = fs::read_to_string(queries_path)
+        .unwrap_or_else(|err| panic!("Failed to read query file {}: {:?}", queries_path, err));
+    contents
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Extract a percentile from an already-sorted slice of durations using nearest
+/// rank. `percentile` is expressed in the 0..=100 range.
+fn percentile(sorted: &[Duration], percentile: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::default();
+    }
+    let rank = (percentile / 100.0 * sorted.len() as f64).ceil() as usize;
+    let index = rank.saturating_sub(1).min(sorted.len() - 1);
+    sorted[index]
+}
+
+/// Aggregate statistics over a batch of per-query timings.
+struct Stats {
+    min: Duration,
+    mean: Duration,
+    p50: Duration,
+    p90: Duration,
+    p99: Duration,
+    qps: f64,
+}
+
+/// Compute min/mean/p50/p90/p99 and QPS from a vector of timings. The vector is
+/// sorted in place so percentiles can be read off by rank.
+fn compute_stats(timings: &mut Vec<Duration>) -> Stats {
+    timings.sort();
+
+    let count = timings.len() as u32;
+    let total: Duration = timings.iter().sum();
+    let mean = if count == 0 { Duration::default() } else { total / count };
+    let qps = if total.as_secs_f64() > 0.0 {
+        timings.len() as f64 / total.as_secs_f64()
+    } else {
+        0.0
+    };
+
+    Stats {
+        min: timings.first().copied().unwrap_or_default(),
+        mean,
+        p50: percentile(timings, 50.0),
+        p90: percentile(timings, 90.0),
+        p99: percentile(timings, 99.0),
+        qps,
+    }
+}
+
+fn main() {
+    // The query file is taken as the first CLI argument, falling back to
+    // "queries.txt" in the current directory; the iteration count is the
+    // optional second argument.
+    let args: Vec<String> = env::args().collect();
+    let queries_path = args.get(1).cloned().unwrap_or_else(|| "queries.txt".to_string());
+    let iterations: usize = args
+        .get(2)
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(100);
+
+    let current_path = get_current_dir();
+    let index_path = format!("{}/index", current_path);
+    println!("The current directory is: {:?}", index_path);
+
+    if !index_exists(&index_path) {
+        println!("Index does not exist...");
+        return;
+    }
+
+    let queries = read_queries(&queries_path);
+    if queries.is_empty() {
+        println!("No queries found in {}", queries_path);
+        return;
+    }
+
+    let index = Index::open_in_dir(&index_path).expect("Failed to open index");
+
+    // Re-register the custom tokenizers before building the QueryParser so that
+    // queries touching the stem_en-analysed body field resolve their tokenizer.
+    register_tokenizers(&index);
+
+    let reader = index
+        .reader_builder()
+        .reload_policy(ReloadPolicy::OnCommit)
+        .try_into()
+        .expect("Failed to build index reader");
+
+    let schema: Schema = index.schema();
+    let title_field: Field = schema.get_field("title").unwrap();
+    let body_field: Field = schema.get_field("body").unwrap();
+    let state_field: Field = schema.get_field("state").unwrap();
+    let query_parser = QueryParser::for_index(&index, vec![title_field, body_field, state_field]);
+
+    // Warm up the reader so the first timed iteration does not pay the cost of
+    // faulting the index pages into memory.
+    {
+        let searcher = reader.searcher();
+        for query in &queries {
+            if let Ok(parsed) = query_parser.parse_query(query) {
+                let _ = searcher.search(&parsed, &TopDocs::with_limit(10));
+            }
+        }
+    }
+
+    let mut all_timings: Vec<Duration> = Vec::with_capacity(queries.len() * iterations);
+
+    for query in &queries {
+        let parsed = match query_parser.parse_query(query) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                println!("Skipping unparseable query {:?}: {:?}", query, err);
+                continue;
+            }
+        };
+
+        let mut timings: Vec<Duration> = Vec::with_capacity(iterations);
+        for _ in 0..iterations {
+            let searcher = reader.searcher();
+            let start_time = Instant::now();
+            let _ = searcher.search(&parsed, &TopDocs::with_limit(10));
+            timings.push(start_time.elapsed());
+        }
+
+        let stats = compute_stats(&mut timings);
+        println!(
+            "query {:?}: min={:?} mean={:?} p50={:?} p90={:?} p99={:?} qps={:.1}",
+            query, stats.min, stats.mean, stats.p50, stats.p90, stats.p99, stats.qps
+        );
+
+        all_timings.extend(timings);
+    }
+
+    let aggregate = compute_stats(&mut all_timings);
+    println!("-----------------------------------------------------------------");
+    println!(
+        "aggregate ({} queries x {} iterations): min={:?} mean={:?} p50={:?} p90={:?} p99={:?} qps={:.1}",
+        queries.len(),
+        iterations,
+        aggregate.min,
+        aggregate.mean,
+        aggregate.p50,
+        aggregate.p90,
+        aggregate.p99,
+        aggregate.qps
+    );
+}
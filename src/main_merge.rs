@@ -0,0 +1,92 @@
+extern crate tantivy;
+use tantivy::directory::MmapDirectory;
+use tantivy::Index;
+
+use std::env;
+use std::fs;
+
+/// Get the current directory.
+///
+/// This function returns a `String` representing the current directory.
+fn get_current_dir() -> String {
+    let current_dir = env::current_dir().expect("Failed to get current directory");
+    let current_dir_str = current_dir.to_string_lossy().into_owned();
+    current_dir_str
+}
+
+/// Check if an index exists at the given path.
+fn index_exists(index_path: &str) -> bool {
+    Index::open_in_dir(index_path).is_ok()
+}
+
+/// Sum the sizes of the files in the index directory and return the total in
+/// megabytes. `fs::metadata` on the directory itself only reports the inode
+/// size, so we walk its entries to get the real on-disk size of the segment
+/// files, which is what makes the before/after merge delta meaningful.
+fn get_index_size(index_location: &str) -> f64 {
+    let mut size: u64 = 0;
+    if let Ok(entries) = fs::read_dir(index_location) {
+        for entry in entries.flatten() {
+            if let Ok(metadata) = entry.metadata() {
+                if metadata.is_file() {
+                    size += metadata.len();
+                }
+            }
+        }
+    }
+    let size_in_megabytes = (size as f64) / (1024.0 * 1024.0);
+
+    size_in_megabytes
+}
+
+fn main() {
+    // Set the index directory in the project's root folder
+    let current_path = get_current_dir();
+    // Concatenar la carpeta "index"
+    let index_path = format!("{}/index", current_path);
+    println!("The current directory is: {:?}", index_path);
+
+    if !index_exists(&index_path) {
+        println!("Index does not exist...");
+        return;
+    }
+
+    let directory = MmapDirectory::open(&index_path).expect("Failed to open index directory");
+    let index = Index::open(directory).expect("Failed to open index");
+
+    // List the segments left behind by the frequent-commit indexing loop.
+    let segment_ids = index
+        .searchable_segment_ids()
+        .expect("Failed to list segment ids");
+    println!("Segments before merge: {}", segment_ids.len());
+    println!("Index size before merge: {} megabytes", get_index_size(&index_path));
+
+    if segment_ids.len() <= 1 {
+        println!("Nothing to merge; the index already has a single segment.");
+        return;
+    }
+
+    // Open a writer with a large heap and merge every segment into one,
+    // blocking until the merge completes.
+    let mut index_writer = index.writer(500_000_000).expect("Failed to open index writer");
+    // Merge every segment into one, blocking until the merge completes.
+    match index_writer.merge(&segment_ids).wait() {
+        Ok(_segment_meta) => println!("Merge completed successfully!"),
+        Err(err) => {
+            println!("Error merging segments: {:?}", err);
+            return;
+        }
+    }
+    // Wait for the background merging threads to finish and drop the now-merged
+    // segments.
+    if let Err(err) = index_writer.wait_merging_threads() {
+        println!("Error waiting on merging threads: {:?}", err);
+        return;
+    }
+
+    let segment_ids_after = index
+        .searchable_segment_ids()
+        .expect("Failed to list segment ids");
+    println!("Segments after merge: {}", segment_ids_after.len());
+    println!("Index size after merge: {} megabytes", get_index_size(&index_path));
+}
@@ -5,11 +5,20 @@ use tantivy::collector::TopDocs;
 use tantivy::directory::MmapDirectory;
 use tantivy::ReloadPolicy;
 use tantivy::Result;
+use tantivy::query::BooleanQuery;
+use tantivy::query::FuzzyTermQuery;
+use tantivy::query::Occur;
+use tantivy::query::Query;
 use tantivy::query::QueryParser;
+use tantivy::query::TermQuery;
 use tantivy::schema::Field;
+use tantivy::schema::FieldType;
+use tantivy::schema::IndexRecordOption;
 use tantivy::schema::Schema;
-use tantivy::tokenizer::TokenizerManager;
+use tantivy::tokenizer::{Language, LowerCaser, NgramTokenizer, SimpleTokenizer, Stemmer, TextAnalyzer, TokenizerManager};
+use tantivy::Term;
 
+use std::cmp::Reverse;
 use std::env;
 use std::fs;
 use std::time::Instant;
@@ -52,8 +61,107 @@ fn read_index(index_path: &str) -> Result<Index> {
     Index::open_in_dir(index_path)
 }
 
+/// Register the custom tokenizer pipelines on the index so query analysis uses
+/// the very same `stem_en` and `autocomplete` analysers that were applied at
+/// indexing time. Tokenizer registrations live in-process, so they have to be
+/// re-declared every time the index is opened.
+fn register_tokenizers(index: &Index) {
+    let stem_en = TextAnalyzer::from(SimpleTokenizer)
+        .filter(LowerCaser)
+        .filter(Stemmer::new(Language::English));
+    index.tokenizers().register("stem_en", stem_en);
+
+    let autocomplete = TextAnalyzer::from(NgramTokenizer::new(2, 10, true)).filter(LowerCaser);
+    index.tokenizers().register("autocomplete", autocomplete);
+}
+
+/// Choose the Levenshtein edit distance allowed for a term based on its length.
+///
+/// Short terms are matched exactly (a single typo would turn them into a
+/// completely different word), while longer terms tolerate more edits. The
+/// distance is capped at 2 so the underlying Levenshtein automaton stays cheap.
+fn edit_distance_for(term: &str) -> u8 {
+    match term.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// Resolve the tokenizer name configured for a text field in the schema,
+/// falling back to `default` for non-text fields or fields without explicit
+/// indexing options.
+fn tokenizer_name_for(schema: &Schema, field: Field) -> String {
+    match schema.get_field_entry(field).field_type() {
+        FieldType::Str(text_options) => text_options
+            .get_indexing_options()
+            .map(|indexing| indexing.tokenizer().to_string())
+            .unwrap_or_else(|| "default".to_string()),
+        _ => "default".to_string(),
+    }
+}
+
+/// Build a fuzzy query from `query`: for each field, tokenize the input with
+/// that field's own configured analyzer, and for each resulting term add a
+/// `FuzzyTermQuery` with a length-based edit distance, plus an exact `TermQuery`
+/// on the same term. Analysing per field keeps indexing and query analysis
+/// consistent, so a term queried against the `stem_en`-analysed `body` is
+/// stemmed before it is matched against the stored stems. All clauses are
+/// combined with `Should`: a fuzzy clause scores the same whatever its edit
+/// distance, so the exact clause's BM25 score is what makes more exact matches
+/// rank higher, and a partial match on a single term still returns the document.
+fn build_fuzzy_query(
+    schema: &Schema,
+    tokenizer_manager: &TokenizerManager,
+    fields: &[Field],
+    query: &str,
+) -> Box<dyn Query> {
+    let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+
+    for &field in fields {
+        let tokenizer_name = tokenizer_name_for(schema, field);
+        let mut tokenizer = tokenizer_manager.get(&tokenizer_name).unwrap();
+        let mut token_stream = tokenizer.token_stream(query);
+
+        while let Some(token) = token_stream.next() {
+            let distance = edit_distance_for(&token.text);
+            let term = Term::from_field_text(field, &token.text);
+            let exact_query = TermQuery::new(term.clone(), IndexRecordOption::WithFreqs);
+            clauses.push((Occur::Should, Box::new(exact_query)));
+            // Transpositions are treated as a single edit (Damerau-Levenshtein).
+            let fuzzy_query = FuzzyTermQuery::new(term, distance, true);
+            clauses.push((Occur::Should, Box::new(fuzzy_query)));
+        }
+    }
+
+    Box::new(BooleanQuery::new(clauses))
+}
+
+/// The ordering to apply when sorting by a fast field.
+enum Order {
+    Asc,
+    Desc,
+}
+
 /// Perform a query on the given Index and print the results.
-fn query_index(index: &Index, query: &str, limit: usize) -> tantivy::Result<Vec<Document>> {
+///
+/// When `fuzzy` is set, the input is run through a typo-tolerant
+/// `FuzzyTermQuery`/`BooleanQuery` path instead of the exact `QueryParser` path,
+/// so misspelled queries still match.
+///
+/// When `sort_field` names a `u64` fast field, results are ordered by that field
+/// and the fast-field value is returned alongside each document: descending via
+/// `TopDocs::order_by_fast_field`, ascending by scoring on the inverted value so
+/// the collector keeps the smallest values. An unknown field name falls back to
+/// BM25 score order, as does the case where no sort field is given.
+fn query_index(
+    index: &Index,
+    query: &str,
+    limit: usize,
+    fuzzy: bool,
+    sort_field: Option<&str>,
+    order: Order,
+) -> tantivy::Result<Vec<(f64, Document)>> {
     println!("Querying the index searching for '{:?}'", query);
 
     let reader = index
@@ -67,32 +175,94 @@ fn query_index(index: &Index, query: &str, limit: usize) -> tantivy::Result<Vec<
     let body_field: Field = schema.get_field("body").unwrap();
     let state_field: Field = schema.get_field("state").unwrap();
 
-    // Crea un TokenizerManager para el análisis de texto
-    let tokenizer_manager = TokenizerManager::default();
+    // Usa el TokenizerManager del índice, que ya tiene registradas las
+    // tuberías personalizadas (stem_en / autocomplete).
+    let tokenizer_manager: TokenizerManager = index.tokenizers().clone();
 
-    // Crea un QueryParser con el esquema, el TokenizerManager y los campos definidos
-    let query_parser = QueryParser::new(schema.clone(), vec![title_field, body_field, state_field], tokenizer_manager);
-    //let query_parser = QueryParser::new(schema.clone(), vec![state_field], tokenizer_manager);
+    // Construye la consulta: modo difuso (tolerante a erratas) o el parseo exacto.
+    let query: Box<dyn Query> = if fuzzy {
+        build_fuzzy_query(schema, &tokenizer_manager, &[title_field, body_field], query)
+    } else {
+        // Crea un QueryParser con el esquema, el TokenizerManager y los campos definidos
+        let query_parser = QueryParser::new(
+            schema.clone(),
+            vec![title_field, body_field, state_field],
+            tokenizer_manager,
+        );
+        // Parsea la consulta
+        query_parser.parse_query(query)?
+    };
 
-    // Parsea la consulta
-    let query = query_parser.parse_query(query)?;
+    let mut retrieved_docs: Vec<(f64, Document)> = Vec::new();
 
-    // Realiza la búsqueda y obtiene los documentos más relevantes
-    let top_docs: Vec<(f32, tantivy::DocAddress)> = searcher.search(&query, &TopDocs::with_limit(limit))?;
+    // Resolve the sort field if one was requested; an unknown field name falls
+    // back cleanly to score ordering rather than panicking on caller input.
+    let sort = match sort_field {
+        Some(field_name) => match schema.get_field(field_name) {
+            Ok(field) => Some(field),
+            Err(_) => {
+                println!("Unknown sort field {:?}; falling back to score ordering", field_name);
+                None
+            }
+        },
+        None => None,
+    };
 
-    let mut retrieved_docs: Vec<Document> = Vec::new();
+    match sort {
+        // Sort by a u64 fast field instead of by relevance score.
+        Some(field) => match order {
+            Order::Desc => {
+                // `order_by_fast_field` keeps the `limit` docs with the highest
+                // field values, i.e. descending order.
+                let collector = TopDocs::with_limit(limit).order_by_fast_field::<u64>(field);
+                let top_docs: Vec<(u64, tantivy::DocAddress)> = searcher.search(&*query, &collector)?;
 
-    // Recorre los documentos encontrados
-    for (_score, doc_address) in top_docs {
-        let retrieved_doc = searcher.doc(doc_address)?;
-        retrieved_docs.push(retrieved_doc);
+                for (value, doc_address) in top_docs {
+                    let retrieved_doc = searcher.doc(doc_address)?;
+                    retrieved_docs.push((value as f64, retrieved_doc));
+                }
+            }
+            Order::Asc => {
+                // To collect the `limit` docs with the *lowest* values we score
+                // each doc by the inverted fast-field value, so the top-docs
+                // collector keeps the smallest values.
+                let collector = TopDocs::with_limit(limit).tweak_score(
+                    move |segment_reader: &tantivy::SegmentReader| {
+                        let fast_field_reader = segment_reader
+                            .fast_fields()
+                            .u64(field)
+                            .expect("sort field is not a u64 fast field");
+                        move |doc: tantivy::DocId, _original_score: tantivy::Score| {
+                            Reverse(fast_field_reader.get(doc))
+                        }
+                    },
+                );
+                let top_docs: Vec<(Reverse<u64>, tantivy::DocAddress)> =
+                    searcher.search(&*query, &collector)?;
+
+                for (Reverse(value), doc_address) in top_docs {
+                    let retrieved_doc = searcher.doc(doc_address)?;
+                    retrieved_docs.push((value as f64, retrieved_doc));
+                }
+            }
+        },
+        // Default path: order by BM25 relevance score.
+        None => {
+            let top_docs: Vec<(f32, tantivy::DocAddress)> =
+                searcher.search(&*query, &TopDocs::with_limit(limit))?;
+
+            for (score, doc_address) in top_docs {
+                let retrieved_doc = searcher.doc(doc_address)?;
+                retrieved_docs.push((score as f64, retrieved_doc));
+            }
+        }
     }
 
     Ok(retrieved_docs)
 }
 
 
-fn print_results(retrieved_docs_result: tantivy::Result<Vec<tantivy::Document>>, index: &tantivy::Index) {
+fn print_results(retrieved_docs_result: tantivy::Result<Vec<(f64, tantivy::Document)>>, index: &tantivy::Index) {
     // Verifica si la consulta tuvo éxito
     if let Ok(retrieved_docs) = retrieved_docs_result {
         // Obtén el esquema del índice
@@ -100,9 +270,10 @@ fn print_results(retrieved_docs_result: tantivy::Result<Vec<tantivy::Document>>,
 
         // Recorre los documentos encontrados
         let mut counter = 0;
-        for retrieved_doc in retrieved_docs {
-            // Trabaja con cada documento según sea necesario
-            println!("Result: {:?} - {}", counter, schema.to_json(&retrieved_doc));
+        for (sort_value, retrieved_doc) in retrieved_docs {
+            // Trabaja con cada documento según sea necesario. El valor de
+            // ordenación es el score BM25 o el valor del fast field.
+            println!("Result: {:?} ({}) - {}", counter, sort_value, schema.to_json(&retrieved_doc));
             counter += 1;
             println!("-----------------------------------------------------------------");
         }
@@ -135,12 +306,23 @@ fn main() {
         if let Ok(index) = read_index(&index_path) {
             // Do something with the read index
             println!("Index read");
+
+            // Re-register the custom tokenizers so query analysis matches the
+            // analysis used at indexing time.
+            register_tokenizers(&index);
             
             let query = "Amazon";
             let search_limit: usize = 20000;
+            // Toggle this to run the typo-tolerant fuzzy path instead of the
+            // exact QueryParser path.
+            let fuzzy = false;
+            // Set to Some("doc_id") to order by that fast field instead of by
+            // relevance score.
+            let sort_field: Option<&str> = None;
 
             let start_time = Instant::now();
-            let retrieved_docs_result = query_index(&index, query, search_limit);
+            let retrieved_docs_result =
+                query_index(&index, query, search_limit, fuzzy, sort_field, Order::Desc);
             let elapsed_time = start_time.elapsed();
             println!("La consulta tomó: {:?} en ejecutarse", elapsed_time);
 
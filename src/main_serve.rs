@@ -0,0 +1,243 @@
+extern crate tantivy;
+use tantivy::Index;
+use tantivy::IndexReader;
+use tantivy::collector::TopDocs;
+use tantivy::directory::MmapDirectory;
+use tantivy::ReloadPolicy;
+use tantivy::query::QueryParser;
+use tantivy::schema::Field;
+use tantivy::schema::Schema;
+use tantivy::tokenizer::{Language, LowerCaser, NgramTokenizer, SimpleTokenizer, Stemmer, TextAnalyzer};
+
+use std::env;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Instant;
+
+/// Get the current directory.
+///
+/// This function returns a `String` representing the current directory.
+fn get_current_dir() -> String {
+    let current_dir = env::current_dir().expect("Failed to get current directory");
+    let current_dir_str = current_dir.to_string_lossy().into_owned();
+    current_dir_str
+}
+
+/// Check if an index exists at the given path.
+fn index_exists(index_path: &str) -> bool {
+    Index::open_in_dir(index_path).is_ok()
+}
+
+/// Register the custom tokenizer pipelines on the index so query analysis uses
+/// the very same `stem_en` and `autocomplete` analysers that were applied at
+/// indexing time. Tokenizer registrations live in-process, so they have to be
+/// re-declared every time the index is opened.
+fn register_tokenizers(index: &Index) {
+    let stem_en = TextAnalyzer::from(SimpleTokenizer)
+        .filter(LowerCaser)
+        .filter(Stemmer::new(Language::English));
+    index.tokenizers().register("stem_en", stem_en);
+
+    let autocomplete = TextAnalyzer::from(NgramTokenizer::new(2, 10, true)).filter(LowerCaser);
+    index.tokenizers().register("autocomplete", autocomplete);
+}
+
+/// Decode the very small subset of percent-encoding that shows up in a
+/// `GET /search?q=...` query string (spaces as `+` or `%20`, and any other
+/// `%XX` escape). This is intentionally minimal: the crate only needs to
+/// recover the original query text, not to be a full URL decoder.
+fn url_decode(input: &str) -> String {
+    // Decode into raw bytes first so multi-byte UTF-8 sequences (e.g. `%C3%A9`)
+    // are reassembled into their original characters.
+    let mut result: Vec<u8> = Vec::with_capacity(input.len());
+    let bytes = input.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => {
+                result.push(b' ');
+                i += 1;
+            }
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+                if let Ok(byte) = u8::from_str_radix(hex, 16) {
+                    result.push(byte);
+                    i += 3;
+                } else {
+                    result.push(b'%');
+                    i += 1;
+                }
+            }
+            other => {
+                result.push(other);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&result).into_owned()
+}
+
+/// Parse the `q` and `limit` parameters out of a request query string such as
+/// `q=united+states&limit=10`. Returns the decoded query and the requested
+/// limit, defaulting to an empty query and a limit of 10. The limit is clamped
+/// to at least 1 so `limit=0` cannot reach `TopDocs::with_limit`, which panics
+/// on a zero limit.
+fn parse_query_string(query_string: &str) -> (String, usize) {
+    let mut query = String::new();
+    let mut limit: usize = 10;
+    for pair in query_string.split('&') {
+        let mut parts = pair.splitn(2, '=');
+        let key = parts.next().unwrap_or("");
+        let value = parts.next().unwrap_or("");
+        match key {
+            "q" => query = url_decode(value),
+            "limit" => {
+                if let Ok(parsed) = url_decode(value).parse::<usize>() {
+                    limit = parsed.max(1);
+                }
+            }
+            _ => {}
+        }
+    }
+    (query, limit)
+}
+
+/// Run the query against the shared reader and return a JSON payload holding the
+/// matching documents, the hit count and the elapsed time. This is the same
+/// `QueryParser`/`TopDocs` path used by the one-shot binaries, but driven off a
+/// reader that is opened once and kept alive across requests.
+fn search(
+    reader: &IndexReader,
+    schema: &Schema,
+    query_parser: &QueryParser,
+    query: &str,
+    limit: usize,
+) -> tantivy::Result<String> {
+    let searcher = reader.searcher();
+
+    let start_time = Instant::now();
+    let parsed_query = query_parser.parse_query(query)?;
+    let top_docs = searcher.search(&parsed_query, &TopDocs::with_limit(limit))?;
+
+    let mut results: Vec<String> = Vec::new();
+    for (_score, doc_address) in &top_docs {
+        let retrieved_doc = searcher.doc(*doc_address)?;
+        results.push(schema.to_json(&retrieved_doc));
+    }
+    let elapsed_time = start_time.elapsed();
+
+    let body = format!(
+        "{{\"hits\":{},\"elapsed_ms\":{},\"results\":[{}]}}",
+        top_docs.len(),
+        elapsed_time.as_millis(),
+        results.join(",")
+    );
+    Ok(body)
+}
+
+/// Write a minimal HTTP/1.1 response back to the client.
+fn write_response(stream: &mut TcpStream, status: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    if let Err(err) = stream.write_all(response.as_bytes()) {
+        println!("Error writing response: {:?}", err);
+    }
+}
+
+/// Handle a single connection: parse the request line, and if it is a
+/// `GET /search?...` run the query. Other methods get a 405, other paths a 404,
+/// and a request without a `q` parameter a 400.
+fn handle_connection(
+    mut stream: TcpStream,
+    reader: &IndexReader,
+    schema: &Schema,
+    query_parser: &QueryParser,
+) {
+    let mut buf_reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    if buf_reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+
+    // The request line looks like "GET /search?q=...&limit=... HTTP/1.1".
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let target = parts.next().unwrap_or("");
+
+    if method != "GET" {
+        write_response(&mut stream, "405 Method Not Allowed", "{\"error\":\"method not allowed\"}");
+        return;
+    }
+
+    let mut target_parts = target.splitn(2, '?');
+    let path = target_parts.next().unwrap_or("");
+    let query_string = target_parts.next().unwrap_or("");
+
+    if path != "/search" {
+        write_response(&mut stream, "404 Not Found", "{\"error\":\"not found\"}");
+        return;
+    }
+
+    let (query, limit) = parse_query_string(query_string);
+    if query.is_empty() {
+        write_response(&mut stream, "400 Bad Request", "{\"error\":\"missing q parameter\"}");
+        return;
+    }
+
+    match search(reader, schema, query_parser, &query, limit) {
+        Ok(body) => write_response(&mut stream, "200 OK", &body),
+        Err(err) => {
+            let body = format!("{{\"error\":\"{:?}\"}}", err);
+            write_response(&mut stream, "500 Internal Server Error", &body);
+        }
+    }
+}
+
+fn main() {
+    // Set the index directory in the project's root folder
+    let current_path = get_current_dir();
+    // Concatenate the "index" folder
+    let index_path = format!("{}/index", current_path);
+    println!("The current directory is: {:?}", index_path);
+
+    if !index_exists(&index_path) {
+        println!("Index does not exist...");
+        return;
+    }
+
+    // Open the index once through an MmapDirectory and keep a single reader
+    // alive for the whole lifetime of the server, reloading on every commit.
+    let directory = MmapDirectory::open(&index_path).expect("Failed to open index directory");
+    let index = Index::open(directory).expect("Failed to open index");
+
+    // Re-register the custom tokenizers before building the QueryParser so that
+    // queries touching the stem_en-analysed body field resolve their tokenizer.
+    register_tokenizers(&index);
+
+    let reader = index
+        .reader_builder()
+        .reload_policy(ReloadPolicy::OnCommit)
+        .try_into()
+        .expect("Failed to build index reader");
+
+    let schema: Schema = index.schema();
+    let title_field: Field = schema.get_field("title").unwrap();
+    let body_field: Field = schema.get_field("body").unwrap();
+    let state_field: Field = schema.get_field("state").unwrap();
+    let query_parser = QueryParser::for_index(&index, vec![title_field, body_field, state_field]);
+
+    let address = "127.0.0.1:3000";
+    let listener = TcpListener::bind(address).expect("Failed to bind address");
+    println!("Listening on http://{}/search?q=...&limit=...", address);
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => handle_connection(stream, &reader, &schema, &query_parser),
+            Err(err) => println!("Connection error: {:?}", err),
+        }
+    }
+}